@@ -0,0 +1,26 @@
+////////////////////////////////////////////////////////////////////////////////
+//
+// Copyright (c) 2018, the Perspective Authors.
+//
+// This file is part of the Perspective library, distributed under the terms
+// of the Apache License 2.0.  The full license can be found in the LICENSE
+// file.
+
+use yew::prelude::*;
+
+/// Implemented by the `Properties` of a component that can take part in a
+/// reorderable, drag-and-droppable list (e.g. `FilterItemProperties`).
+pub trait DragDropListItemProps: Properties {
+    type Item: Clone;
+
+    fn get_item(&self) -> Self::Item;
+
+    /// This item's location within whatever recursive container holds it,
+    /// e.g. the path into a `FilterGroup` tree. Defaults to the root so
+    /// flat, non-nested lists don't need to implement it. A container that
+    /// nests (like `FilterGroupItem`) uses this to support moving an item
+    /// between sibling containers rather than only reordering within one.
+    fn get_path(&self) -> Vec<usize> {
+        vec![]
+    }
+}
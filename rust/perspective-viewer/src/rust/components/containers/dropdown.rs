@@ -0,0 +1,77 @@
+////////////////////////////////////////////////////////////////////////////////
+//
+// Copyright (c) 2018, the Perspective Authors.
+//
+// This file is part of the Perspective library, distributed under the terms
+// of the Apache License 2.0.  The full license can be found in the LICENSE
+// file.
+
+use web_sys::*;
+use yew::prelude::*;
+
+/// A generic `<select>`-backed dropdown over any `ToString`-able value list,
+/// e.g. `DropDown<FilterOp>` or `DropDown<String>`.
+pub struct DropDown<T: Clone + PartialEq + ToString + 'static> {
+    props: DropDownProperties<T>,
+    link: ComponentLink<Self>,
+}
+
+pub enum DropDownMsg {
+    Select(usize),
+}
+
+#[derive(Properties, Clone)]
+pub struct DropDownProperties<T: Clone + PartialEq + ToString + 'static> {
+    pub values: Vec<T>,
+    pub selected: T,
+    pub on_select: Callback<T>,
+
+    #[prop_or_default]
+    pub class: String,
+
+    #[prop_or_default]
+    pub auto_resize: bool,
+}
+
+impl<T: Clone + PartialEq + ToString + 'static> Component for DropDown<T> {
+    type Message = DropDownMsg;
+    type Properties = DropDownProperties<T>;
+
+    fn create(props: DropDownProperties<T>, link: ComponentLink<Self>) -> Self {
+        DropDown { props, link }
+    }
+
+    fn update(&mut self, msg: DropDownMsg) -> bool {
+        let DropDownMsg::Select(i) = msg;
+        if let Some(value) = self.props.values.get(i) {
+            self.props.on_select.emit(value.clone());
+        }
+
+        false
+    }
+
+    fn change(&mut self, props: DropDownProperties<T>) -> bool {
+        self.props = props;
+        true
+    }
+
+    fn view(&self) -> Html {
+        let change = self.link.callback(|event: ChangeData| match event {
+            ChangeData::Select(select) => DropDownMsg::Select(select.selected_index() as usize),
+            _ => DropDownMsg::Select(0),
+        });
+
+        html! {
+            <select class={ self.props.class.clone() } onchange={ change }>
+                {
+                    for self.props.values.iter().map(|value| {
+                        let selected = *value == self.props.selected;
+                        html! {
+                            <option selected={ selected }>{ value.to_string() }</option>
+                        }
+                    })
+                }
+            </select>
+        }
+    }
+}
@@ -0,0 +1,189 @@
+////////////////////////////////////////////////////////////////////////////////
+//
+// Copyright (c) 2018, the Perspective Authors.
+//
+// This file is part of the Perspective library, distributed under the terms
+// of the Apache License 2.0.  The full license can be found in the LICENSE
+// file.
+
+//! A single `FilterGroup` node: an AND/OR toggle plus its child conditions,
+//! indented one level for every nested sub-group. Children render as either
+//! a `FilterItem` leaf or another, recursively-nested `FilterGroupItem`.
+
+use crate::config::*;
+use crate::custom_elements::filter_dropdown::*;
+use crate::dragdrop::*;
+use crate::renderer::*;
+use crate::session::*;
+use crate::*;
+
+use super::filter_item::{get_group_mut, remove_condition_by_path, FilterItem};
+
+use web_sys::*;
+use yew::prelude::*;
+
+pub struct FilterGroupItem {
+    props: FilterGroupItemProperties,
+    link: ComponentLink<Self>,
+}
+
+pub enum FilterGroupItemMsg {
+    ToggleOp,
+    Drop(DragEvent),
+}
+
+#[derive(Properties, Clone)]
+pub struct FilterGroupItemProperties {
+    /// The path to this group, e.g. `[]` for the root or `[1]` for the
+    /// second condition's sub-group. Mirrors `FilterItemProperties::path`.
+    pub path: Vec<usize>,
+    pub filter_dropdown: FilterDropDownElement,
+    pub on_keydown: Callback<String>,
+    pub session: Session,
+    pub renderer: Renderer,
+    pub dragdrop: DragDrop,
+}
+
+derive_renderable_props!(FilterGroupItemProperties);
+
+impl FilterGroupItemProperties {
+    /// Read-only snapshot of the `FilterGroup` this component renders.
+    fn group(&self) -> FilterGroup {
+        let ViewConfig { mut filter, .. } = self.session.get_view_config();
+        get_group_mut(&mut filter, &self.path).clone()
+    }
+
+    /// Flip this group's combinator between `AND` and `OR`.
+    fn toggle_op(&self) {
+        let ViewConfig { mut filter, .. } = self.session.get_view_config();
+        let group = get_group_mut(&mut filter, &self.path);
+        group.op = match group.op {
+            LogicalOp::And => LogicalOp::Or,
+            LogicalOp::Or => LogicalOp::And,
+        };
+
+        self.update_and_render(ViewConfigUpdate {
+            filter: Some(filter),
+            ..ViewConfigUpdate::default()
+        });
+    }
+
+    /// Relocate the condition at `source_path` into this group, e.g. when a
+    /// `FilterItem` is dropped on this group's drop target. Keyed by path
+    /// rather than column name, so two conditions on the same column in
+    /// different groups can't be confused with each other.
+    fn move_into_group(&self, source_path: &[usize]) {
+        let ViewConfig { mut filter, .. } = self.session.get_view_config();
+        if let Some(condition) = remove_condition_by_path(&mut filter, source_path) {
+            // Removing the source shifts its later siblings down by one, and
+            // this group (or one of its ancestors) may be one of them.
+            let mut target = self.path.clone();
+            let depth = source_path.len() - 1;
+            if depth < target.len()
+                && target[..depth] == source_path[..depth]
+                && source_path[depth] < target[depth]
+            {
+                target[depth] -= 1;
+            }
+
+            get_group_mut(&mut filter, &target)
+                .filters
+                .push(condition);
+
+            self.update_and_render(ViewConfigUpdate {
+                filter: Some(filter),
+                ..ViewConfigUpdate::default()
+            });
+        }
+    }
+}
+
+impl Component for FilterGroupItem {
+    type Message = FilterGroupItemMsg;
+    type Properties = FilterGroupItemProperties;
+
+    fn create(props: FilterGroupItemProperties, link: ComponentLink<Self>) -> Self {
+        FilterGroupItem { props, link }
+    }
+
+    fn update(&mut self, msg: FilterGroupItemMsg) -> bool {
+        match msg {
+            FilterGroupItemMsg::ToggleOp => {
+                self.props.toggle_op();
+                false
+            }
+            FilterGroupItemMsg::Drop(event) => {
+                event.prevent_default();
+                if let Some(path) = self.props.dragdrop.get_drag_path() {
+                    self.props.move_into_group(&path);
+                    self.props.dragdrop.drag_end();
+                }
+                false
+            }
+        }
+    }
+
+    fn change(&mut self, props: FilterGroupItemProperties) -> bool {
+        self.props = props;
+        true
+    }
+
+    fn view(&self) -> Html {
+        let group = self.props.group();
+        let op_label = match group.op {
+            LogicalOp::And => "AND",
+            LogicalOp::Or => "OR",
+        };
+
+        let toggle = self.link.callback(|_: MouseEvent| FilterGroupItemMsg::ToggleOp);
+        let ondragover = Callback::from(|event: DragEvent| event.prevent_default());
+        let ondrop = self.link.callback(FilterGroupItemMsg::Drop);
+
+        html! {
+            <div class="filter-group" ondragover={ ondragover } ondrop={ ondrop }>
+                <button class="filter-group-op-toggle" onclick={ toggle }>{ op_label }</button>
+                <div class="filter-group-children">
+                    {
+                        for group.filters.iter().enumerate().map(|(i, condition)| {
+                            let mut child_path = self.props.path.clone();
+                            child_path.push(i);
+                            self.render_condition(condition, child_path)
+                        })
+                    }
+                </div>
+            </div>
+        }
+    }
+}
+
+impl FilterGroupItem {
+    /// Render one child of this group: a `FilterItem` for a leaf, or a
+    /// nested, indented `FilterGroupItem` for a sub-group.
+    fn render_condition(&self, condition: &FilterCondition, path: Vec<usize>) -> Html {
+        match condition {
+            FilterCondition::Item(filter) => html! {
+                <FilterItem
+                    filter={ filter.clone() }
+                    path={ path }
+                    filter_dropdown={ self.props.filter_dropdown.clone() }
+                    on_keydown={ self.props.on_keydown.clone() }
+                    session={ self.props.session.clone() }
+                    renderer={ self.props.renderer.clone() }
+                    dragdrop={ self.props.dragdrop.clone() }>
+                </FilterItem>
+            },
+            FilterCondition::Group(_) => html! {
+                <div class="filter-group-indent">
+                    <FilterGroupItem
+                        path={ path }
+                        filter_dropdown={ self.props.filter_dropdown.clone() }
+                        on_keydown={ self.props.on_keydown.clone() }
+                        session={ self.props.session.clone() }
+                        renderer={ self.props.renderer.clone() }
+                        dragdrop={ self.props.dragdrop.clone() }>
+                    </FilterGroupItem>
+                </div>
+            },
+        }
+    }
+}
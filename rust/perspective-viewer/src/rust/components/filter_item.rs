@@ -20,15 +20,112 @@ use chrono::{Local, NaiveDate, TimeZone};
 use web_sys::*;
 use yew::prelude::*;
 
+/// Split a `Scalar::DateTime`'s millisecond timestamp into the `%Y-%m-%d`
+/// and `%H:%M:%S` strings that back a `Type::Datetime` filter's two
+/// `<input>`s.
+fn split_datetime(millis: u64) -> (String, String) {
+    let rescaled = millis as i64;
+    if rescaled <= 0 {
+        return (String::new(), String::new());
+    }
+
+    match Local.timestamp_opt(rescaled / 1000, ((rescaled % 1000) * 1000) as u32) {
+        chrono::LocalResult::Single(x) => (
+            x.format("%Y-%m-%d").to_string(),
+            x.format("%H:%M:%S").to_string(),
+        ),
+        _ => (String::new(), String::new()),
+    }
+}
+
+/// Walk a (possibly nested) `FilterGroup` down to the leaf `Filter`
+/// addressed by `path`, where each element selects a child index at that
+/// depth. Mirrors the implicit top-level `AND` group a flat `filter: Vec`
+/// used to be.
+pub(crate) fn get_filter_mut<'a>(group: &'a mut FilterGroup, path: &[usize]) -> &'a mut Filter {
+    match path {
+        [] => panic!("Empty filter path"),
+        [idx] => match group.filters.get_mut(*idx).expect("Filter on no column") {
+            FilterCondition::Item(filter) => filter,
+            FilterCondition::Group(_) => panic!("Path does not address a leaf filter"),
+        },
+        [idx, rest @ ..] => match group.filters.get_mut(*idx).expect("Filter on no column") {
+            FilterCondition::Group(sub_group) => get_filter_mut(sub_group, rest),
+            FilterCondition::Item(_) => panic!("Path descends past a leaf filter"),
+        },
+    }
+}
+
+/// Walk a (possibly nested) `FilterGroup` down to the sub-group addressed
+/// by `path`; an empty path is the root group itself. Used by
+/// `FilterGroupItem` to read/update a group's combinator and children.
+pub(crate) fn get_group_mut<'a>(group: &'a mut FilterGroup, path: &[usize]) -> &'a mut FilterGroup {
+    match path {
+        [] => group,
+        [idx, rest @ ..] => match group.filters.get_mut(*idx).expect("Filter on no column") {
+            FilterCondition::Group(sub_group) => get_group_mut(sub_group, rest),
+            FilterCondition::Item(_) => panic!("Path descends past a leaf filter"),
+        },
+    }
+}
+
+/// Remove and return the condition addressed by `path`, used to relocate a
+/// dragged `FilterItem`/`FilterGroupItem` into a different group. Keyed by
+/// path rather than column name, since two conditions on the same column
+/// in different groups (e.g. `price > 10 AND price < 20`) would otherwise
+/// be indistinguishable. Returns `None` if `path` no longer addresses
+/// anything, e.g. the tree changed between drag-start and drop.
+pub(crate) fn remove_condition_by_path(
+    group: &mut FilterGroup,
+    path: &[usize],
+) -> Option<FilterCondition> {
+    match path {
+        [] => None,
+        [idx] => {
+            if *idx < group.filters.len() {
+                Some(group.filters.remove(*idx))
+            } else {
+                None
+            }
+        }
+        [idx, rest @ ..] => match group.filters.get_mut(*idx)? {
+            FilterCondition::Group(sub_group) => remove_condition_by_path(sub_group, rest),
+            FilterCondition::Item(_) => None,
+        },
+    }
+}
+
 /// A control for a single filter condition.
 pub struct FilterItem {
     props: FilterItemProperties,
     link: ComponentLink<FilterItem>,
     input: String,
+
+    /// The `date` and `time` parts of a `Type::Datetime` filter's value,
+    /// held separately because they come from two distinct `<input>`s and
+    /// are only recombined into a single `Scalar::DateTime` on `oninput`.
+    date: String,
+    time: String,
+
+    /// Committed pills for a `FilterOp::In` filter, and the text of the
+    /// not-yet-committed token currently being typed.
+    tokens: Vec<String>,
+    token_input: String,
+}
+
+/// Which half of a `Type::Datetime` filter's value an `<input>` edits.
+pub enum DateTimePart {
+    Date,
+    Time,
 }
 
 pub enum FilterItemMsg {
-    FilterInput((usize, String), String, HtmlElement),
+    FilterInput((Vec<usize>, String), String, HtmlElement),
+    DateTimeInput(DateTimePart, String),
+    TokenInput(String, HtmlElement),
+    TokenCommit,
+    TokenBackspace,
+    TokenRemove(usize),
     Close,
     FilterOpSelect(FilterOp),
     FilterKeyDown(u32),
@@ -37,7 +134,11 @@ pub enum FilterItemMsg {
 #[derive(Properties, Clone)]
 pub struct FilterItemProperties {
     pub filter: Filter,
-    pub idx: usize,
+
+    /// The sequence of child indices that locates this condition within the
+    /// root `FilterGroup`, e.g. `[0, 2]` is the third condition of the first
+    /// sub-group. A top-level condition has a single-element path.
+    pub path: Vec<usize>,
     pub filter_dropdown: FilterDropDownElement,
     pub on_keydown: Callback<String>,
     pub session: Session,
@@ -53,12 +154,24 @@ impl DragDropListItemProps for FilterItemProperties {
     fn get_item(&self) -> Filter {
         self.filter.clone()
     }
+
+    fn get_path(&self) -> Vec<usize> {
+        self.path.clone()
+    }
 }
 
 impl FilterItemProperties {
     /// Does this filter item get a "suggestions" auto-complete modal?
     fn is_suggestable(&self) -> bool {
-        self.filter.1 == FilterOp::EQ && self.get_filter_type() == Type::String
+        self.get_filter_type() == Type::String
+            && matches!(
+                self.filter.1,
+                FilterOp::EQ
+                    | FilterOp::Contains
+                    | FilterOp::BeginsWith
+                    | FilterOp::EndsWith
+                    | FilterOp::In
+            )
     }
 
     /// Get this filter's type, e.g. the type of the column.
@@ -83,6 +196,8 @@ impl FilterItemProperties {
                 FilterOp::Contains,
                 FilterOp::EndsWith,
                 FilterOp::In,
+                FilterOp::Regex,
+                FilterOp::Like,
                 FilterOp::IsNotNull,
                 FilterOp::IsNull,
             ],
@@ -105,7 +220,7 @@ impl FilterItemProperties {
     /// - `op` The new `FilterOp`.
     fn update_filter_op(&self, op: FilterOp) {
         let ViewConfig { mut filter, .. } = self.session.get_view_config();
-        let filter_item = &mut filter.get_mut(self.idx).expect("Filter on no column");
+        let filter_item = get_filter_mut(&mut filter, &self.path);
         filter_item.1 = op;
         let update = ViewConfigUpdate {
             filter: Some(filter),
@@ -121,14 +236,13 @@ impl FilterItemProperties {
     /// - `val` The new filter value.
     fn update_filter_value(&self, val: String) {
         let ViewConfig { mut filter, .. } = self.session.get_view_config();
-        let filter_item = &mut filter.get_mut(self.idx).expect("Filter on no column");
+        let filter_item = get_filter_mut(&mut filter, &self.path);
         match filter_item.1 {
-            FilterOp::In => {
-                filter_item.2 = FilterTerm::Array(
-                    val.split(',')
-                        .map(|x| Scalar::String(x.trim().to_owned()))
-                        .collect(),
-                );
+            // Regex/LIKE patterns are only offered for string columns and are
+            // stored verbatim, since the pattern itself (e.g. `^AA.*`,
+            // `%CORP%`) is not a value to coerce.
+            FilterOp::Regex | FilterOp::Like => {
+                filter_item.2 = FilterTerm::Scalar(Scalar::String(val));
             }
             _ => match self.get_filter_type() {
                 Type::String => {
@@ -159,6 +273,14 @@ impl FilterItemProperties {
                         _ => Scalar::Null,
                     })
                 }
+                Type::Datetime => {
+                    filter_item.2 = FilterTerm::Scalar(
+                        match chrono::NaiveDateTime::parse_from_str(&val, "%Y-%m-%d %H:%M:%S") {
+                            Ok(datetime) => Scalar::DateTime(datetime.timestamp_millis() as u64),
+                            _ => Scalar::Null,
+                        },
+                    )
+                }
                 _ => {}
             },
         }
@@ -170,6 +292,48 @@ impl FilterItemProperties {
 
         self.update_and_render(update);
     }
+
+    /// Update a `FilterOp::In` filter's value from the chip/token editor's
+    /// committed pills, typing each one per-column rather than naively
+    /// splitting a comma-separated string. A numeric column's unparseable
+    /// token is dropped rather than coerced to `0`, matching
+    /// `update_filter_value`'s behavior of leaving a bad value alone.
+    ///
+    /// # Arguments
+    /// - `tokens` The committed pills, in order.
+    fn update_filter_tokens(&self, tokens: &[String]) {
+        let ViewConfig { mut filter, .. } = self.session.get_view_config();
+        let filter_item = get_filter_mut(&mut filter, &self.path);
+        let col_type = self.get_filter_type();
+        filter_item.2 = FilterTerm::Array(
+            tokens
+                .iter()
+                .filter_map(|token| match col_type {
+                    Type::Integer | Type::Float => {
+                        token.trim().parse::<f64>().ok().map(Scalar::Float)
+                    }
+                    _ => Some(Scalar::String(token.trim().to_owned())),
+                })
+                .collect(),
+        );
+
+        let update = ViewConfigUpdate {
+            filter: Some(filter),
+            ..ViewConfigUpdate::default()
+        };
+
+        self.update_and_render(update);
+    }
+
+    /// Is `token` acceptable as a new pill for this filter's column type?
+    /// Numeric columns reject tokens that won't parse, rather than silently
+    /// admitting them and coercing the stored value to `0`.
+    fn is_valid_token(&self, token: &str) -> bool {
+        match self.get_filter_type() {
+            Type::Integer | Type::Float => token.trim().parse::<f64>().is_ok(),
+            _ => true,
+        }
+    }
 }
 
 type FilterOpSelector = DropDown<FilterOp>;
@@ -190,7 +354,25 @@ impl Component for FilterItem {
             x => format!("{}", x),
         };
 
-        FilterItem { props, link, input }
+        let (date, time) = match &props.filter.2 {
+            FilterTerm::Scalar(Scalar::DateTime(x)) => split_datetime(*x),
+            _ => (String::new(), String::new()),
+        };
+
+        let tokens = match &props.filter.2 {
+            FilterTerm::Array(scalars) => scalars.iter().map(|x| format!("{}", x)).collect(),
+            _ => vec![],
+        };
+
+        FilterItem {
+            props,
+            link,
+            input,
+            date,
+            time,
+            tokens,
+            token_input: String::new(),
+        }
     }
 
     fn update(&mut self, msg: FilterItemMsg) -> bool {
@@ -198,8 +380,12 @@ impl Component for FilterItem {
             FilterItemMsg::FilterInput(column, input, target) => {
                 self.input = input.clone();
                 if self.props.is_suggestable() {
+                    // Rank suggestions to match the operator: substring for
+                    // `Contains`, prefix for `BeginsWith`, suffix for
+                    // `EndsWith`, prefix for everything else.
                     self.props.filter_dropdown.autocomplete(
                         column,
+                        self.props.filter.1,
                         input.clone(),
                         target,
                     );
@@ -208,17 +394,74 @@ impl Component for FilterItem {
                 self.props.update_filter_value(input);
                 false
             }
+            FilterItemMsg::DateTimeInput(DateTimePart::Date, date) => {
+                self.date = date;
+                self.props
+                    .update_filter_value(format!("{} {}", self.date, self.time));
+                false
+            }
+            FilterItemMsg::DateTimeInput(DateTimePart::Time, time) => {
+                self.time = time;
+                self.props
+                    .update_filter_value(format!("{} {}", self.date, self.time));
+                false
+            }
+            FilterItemMsg::TokenInput(value, target) => {
+                self.token_input = value.clone();
+                if self.props.is_suggestable() {
+                    let column = (self.props.path.clone(), self.props.filter.0.clone());
+                    // `In` suggests against the token currently being typed,
+                    // not the whole (possibly multi-pill) field.
+                    self.props
+                        .filter_dropdown
+                        .autocomplete(column, self.props.filter.1, value, target);
+                }
+                true
+            }
+            FilterItemMsg::TokenCommit => {
+                if self.props.is_suggestable() {
+                    if let Some(value) = self.props.filter_dropdown.item_select() {
+                        self.token_input = value;
+                    }
+                    self.props.filter_dropdown.hide().unwrap();
+                }
+
+                let token = self.token_input.trim();
+                if !token.is_empty() && self.props.is_valid_token(token) {
+                    self.tokens.push(token.to_owned());
+                    self.token_input.clear();
+                    self.props.update_filter_tokens(&self.tokens);
+                }
+                true
+            }
+            FilterItemMsg::TokenBackspace => {
+                if self.token_input.is_empty() && self.tokens.pop().is_some() {
+                    self.props.update_filter_tokens(&self.tokens);
+                    true
+                } else {
+                    false
+                }
+            }
+            FilterItemMsg::TokenRemove(i) => {
+                self.tokens.remove(i);
+                self.props.update_filter_tokens(&self.tokens);
+                true
+            }
             FilterItemMsg::FilterKeyDown(40) => {
                 if self.props.is_suggestable() {
                     self.props.filter_dropdown.item_down();
-                    self.props.filter_dropdown.item_select();
+                    if let Some(value) = self.props.filter_dropdown.item_select() {
+                        return self.commit_suggestion(value);
+                    }
                 }
                 false
             }
             FilterItemMsg::FilterKeyDown(38) => {
                 if self.props.is_suggestable() {
                     self.props.filter_dropdown.item_up();
-                    self.props.filter_dropdown.item_select();
+                    if let Some(value) = self.props.filter_dropdown.item_select() {
+                        return self.commit_suggestion(value);
+                    }
                 }
                 false
             }
@@ -228,8 +471,12 @@ impl Component for FilterItem {
             }
             FilterItemMsg::FilterKeyDown(13) => {
                 if self.props.is_suggestable() {
-                    self.props.filter_dropdown.item_select();
+                    let committed = match self.props.filter_dropdown.item_select() {
+                        Some(value) => self.commit_suggestion(value),
+                        None => false,
+                    };
                     self.props.filter_dropdown.hide().unwrap();
+                    return committed;
                 }
                 false
             }
@@ -258,6 +505,14 @@ impl Component for FilterItem {
                         self.input = x.format("%Y-%m-%d").to_string();
                     }
                 }
+
+                let (date, time) = split_datetime(*x);
+                self.date = date;
+                self.time = time;
+            }
+            FilterTerm::Array(scalars) => {
+                self.tokens = scalars.iter().map(|x| format!("{}", x)).collect();
+                self.input = format!("{}", &props.filter.2);
             }
             x => self.input = format!("{}", x),
         };
@@ -267,7 +522,7 @@ impl Component for FilterItem {
     }
 
     fn view(&self) -> Html {
-        let idx = self.props.idx;
+        let path = self.props.path.clone();
         let filter = self.props.filter.clone();
         let column = filter.0.to_owned();
         let col_type = self
@@ -283,9 +538,10 @@ impl Component for FilterItem {
         let input = self.link.callback({
             let noderef = noderef.clone();
             let column = column.clone();
+            let path = path.clone();
             move |input: InputData| {
                 let target = noderef.cast::<HtmlElement>().unwrap();
-                FilterItemMsg::FilterInput((idx, column.clone()), input.value, target)
+                FilterItemMsg::FilterInput((path.clone(), column.clone()), input.value, target)
             }
         });
 
@@ -294,7 +550,7 @@ impl Component for FilterItem {
             let input = self.input.clone();
             move |_: FocusEvent| {
                 let target = noderef.cast::<HtmlElement>().unwrap();
-                FilterItemMsg::FilterInput((idx, column.clone()), input.clone(), target)
+                FilterItemMsg::FilterInput((path.clone(), column.clone()), input.clone(), target)
             }
         });
 
@@ -303,18 +559,23 @@ impl Component for FilterItem {
             FilterItemMsg::FilterKeyDown(event.key_code())
         });
 
+        let date_input = self.link.callback(|input: InputData| {
+            FilterItemMsg::DateTimeInput(DateTimePart::Date, input.value)
+        });
+
+        let time_input = self.link.callback(|input: InputData| {
+            FilterItemMsg::DateTimeInput(DateTimePart::Time, input.value)
+        });
+
         let dragref = NodeRef::default();
         let dragstart = Callback::from({
-            let event_name = self.props.filter.0.to_owned();
+            let path = self.props.get_path();
             let dragref = dragref.clone();
             let dragdrop = self.props.dragdrop.clone();
             move |event: DragEvent| {
                 let elem = dragref.cast::<HtmlElement>().unwrap();
                 event.data_transfer().unwrap().set_drag_image(&elem, 0, 0);
-                dragdrop.drag_start(
-                    event_name.to_string(),
-                    DragEffect::Move(DropAction::Filter),
-                )
+                dragdrop.drag_start(path.clone(), DragEffect::Move(DropAction::Filter))
             }
         });
 
@@ -324,86 +585,136 @@ impl Component for FilterItem {
             _ => "",
         };
 
-        let input_elem = match col_type {
-            Type::Integer => html! {
-                <input
-                    type="number"
-                    placeholder="Value"
-                    class="num-filter"
-                    step="1"
-                    ref={ noderef.clone() }
-                    onkeydown={ keydown }
-                    onfocus={ focus }
-                    onblur={ blur }
-                    value={ self.input.clone() }
-                    oninput={ input }/>
-            },
-            Type::Float => html! {
-                <input
-                    type="number"
-                    placeholder="Value"
-                    class="num-filter"
-                    ref={ noderef.clone() }
-                    onkeydown={ keydown }
-                    onfocus={ focus }
-                    onblur={ blur }
-                    value={ self.input.clone() }
-                    oninput={ input }/>
-            },
-            Type::String => html! {
-                <input
-                    type="text"
-                    size="4"
-                    placeholder="Value"
-                    class="string-filter"
-                    // TODO This is dirty and it may not work in the future.
-                    onInput="this.parentNode.dataset.value=this.value"
-                    ref={ noderef.clone() }
-                    onkeydown={ keydown }
-                    onfocus={ focus }
-                    onblur={ blur }
-                    value={ self.input.clone() }
-                    oninput={ input }/>
-            },
-            Type::Date => html! {
-                <input
-                    type="date"
-                    placeholder="Value"
-                    class="date-filter"
-                    ref={ noderef.clone() }
-                    onkeydown={ keydown }
-                    onfocus={ focus }
-                    onblur={ blur }
-                    value={ self.input.clone() }
-                    oninput={ input }/>
-            },
-            Type::Datetime => html! {
-                <>
+        let token_noderef = NodeRef::default();
+        let token_input_cb = self.link.callback({
+            let token_noderef = token_noderef.clone();
+            move |input: InputData| {
+                let target = token_noderef.cast::<HtmlElement>().unwrap();
+                FilterItemMsg::TokenInput(input.value, target)
+            }
+        });
+
+        let token_keydown = self.link.callback(|event: KeyboardEvent| match event.key_code() {
+            // `Enter` or `,` commits the in-progress token as a pill.
+            13 | 188 => {
+                event.prevent_default();
+                FilterItemMsg::TokenCommit
+            }
+            8 => FilterItemMsg::TokenBackspace,
+            other => FilterItemMsg::FilterKeyDown(other),
+        });
+
+        let input_elem = if filter.1 == FilterOp::In {
+            html! {
+                <div class="token-filter">
+                    {
+                        for self.tokens.iter().cloned().enumerate().map(|(i, token)| {
+                            let remove = self.link.callback(move |_: MouseEvent| {
+                                FilterItemMsg::TokenRemove(i)
+                            });
+
+                            html! {
+                                <span class="filter-token">
+                                    { token }
+                                    <span class="filter-token-remove" onclick={ remove }>
+                                        { "\u{00d7}" }
+                                    </span>
+                                </span>
+                            }
+                        })
+                    }
                     <input
-                        type="date"
+                        type="text"
+                        size="4"
                         placeholder="Value"
-                        class="date-filter"
-                        ref={ noderef.clone() }
-                        onkeydown={ keydown.clone() }
-                        onfocus={ focus.clone() }
+                        class="string-filter token-input"
+                        ref={ token_noderef }
+                        onkeydown={ token_keydown }
                         onblur={ blur.clone() }
-                        // value={ self.input.clone() }
-                        oninput={ input.clone() }/>
-
+                        value={ self.token_input.clone() }
+                        oninput={ token_input_cb }/>
+                </div>
+            }
+        } else {
+            match col_type {
+                Type::Integer => html! {
                     <input
-                        type="time"
+                        type="number"
                         placeholder="Value"
-                        class="time-filter"
-                        // ref={ noderef.clone() }
+                        class="num-filter"
+                        step="1"
+                        ref={ noderef.clone() }
                         onkeydown={ keydown }
                         onfocus={ focus }
                         onblur={ blur }
-                        // value={ self.input.clone() }
+                        value={ self.input.clone() }
                         oninput={ input }/>
-                </>
-            },
-            _ => {
-                html! {}
+                },
+                Type::Float => html! {
+                    <input
+                        type="number"
+                        placeholder="Value"
+                        class="num-filter"
+                        ref={ noderef.clone() }
+                        onkeydown={ keydown }
+                        onfocus={ focus }
+                        onblur={ blur }
+                        value={ self.input.clone() }
+                        oninput={ input }/>
+                },
+                Type::String => html! {
+                    <input
+                        type="text"
+                        size="4"
+                        placeholder="Value"
+                        class="string-filter"
+                        // TODO This is dirty and it may not work in the future.
+                        onInput="this.parentNode.dataset.value=this.value"
+                        ref={ noderef.clone() }
+                        onkeydown={ keydown }
+                        onfocus={ focus }
+                        onblur={ blur }
+                        value={ self.input.clone() }
+                        oninput={ input }/>
+                },
+                Type::Date => html! {
+                    <input
+                        type="date"
+                        placeholder="Value"
+                        class="date-filter"
+                        ref={ noderef.clone() }
+                        onkeydown={ keydown }
+                        onfocus={ focus }
+                        onblur={ blur }
+                        value={ self.input.clone() }
+                        oninput={ input }/>
+                },
+                Type::Datetime => html! {
+                    <>
+                        <input
+                            type="date"
+                            placeholder="Value"
+                            class="date-filter"
+                            ref={ noderef.clone() }
+                            onkeydown={ keydown.clone() }
+                            onblur={ blur.clone() }
+                            value={ self.date.clone() }
+                            oninput={ date_input }/>
+
+                        <input
+                            type="time"
+                            placeholder="Value"
+                            class="time-filter"
+                            step="1"
+                            onkeydown={ keydown }
+                            onblur={ blur }
+                            value={ self.time.clone() }
+                            oninput={ time_input }/>
+                    </>
+                },
+                _ => {
+                    html! {}
+                }
             }
         };
 
@@ -434,4 +745,105 @@ impl Component for FilterItem {
             </>
         }
     }
+}
+
+impl FilterItem {
+    /// Write a selected suggestion back into whichever field is live (the
+    /// token input for `FilterOp::In`, the plain value input otherwise),
+    /// committing it to the `ViewConfig` exactly as typing it would have.
+    fn commit_suggestion(&mut self, value: String) -> bool {
+        if self.props.filter.1 == FilterOp::In {
+            self.token_input = value;
+        } else {
+            self.input = value.clone();
+            self.props.update_filter_value(value);
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(column: &str) -> FilterCondition {
+        FilterCondition::Item((
+            column.to_owned(),
+            FilterOp::EQ,
+            FilterTerm::Scalar(Scalar::String("x".to_owned())),
+        ))
+    }
+
+    /// `a, (b, c), d` - a root group with a nested sub-group at index 1.
+    fn nested_tree() -> FilterGroup {
+        FilterGroup {
+            op: LogicalOp::And,
+            filters: vec![
+                item("a"),
+                FilterCondition::Group(FilterGroup {
+                    op: LogicalOp::Or,
+                    filters: vec![item("b"), item("c")],
+                }),
+                item("d"),
+            ],
+        }
+    }
+
+    #[test]
+    fn get_filter_mut_addresses_a_top_level_leaf() {
+        let mut tree = nested_tree();
+        assert_eq!(get_filter_mut(&mut tree, &[0]).0, "a");
+    }
+
+    #[test]
+    fn get_filter_mut_addresses_a_leaf_inside_a_sub_group() {
+        let mut tree = nested_tree();
+        assert_eq!(get_filter_mut(&mut tree, &[1, 1]).0, "c");
+    }
+
+    #[test]
+    #[should_panic]
+    fn get_filter_mut_panics_on_a_path_through_a_leaf() {
+        let mut tree = nested_tree();
+        get_filter_mut(&mut tree, &[0, 0]);
+    }
+
+    #[test]
+    fn get_group_mut_empty_path_is_the_root() {
+        let mut tree = nested_tree();
+        assert_eq!(get_group_mut(&mut tree, &[]).filters.len(), 3);
+    }
+
+    #[test]
+    fn get_group_mut_addresses_a_nested_sub_group() {
+        let mut tree = nested_tree();
+        assert_eq!(get_group_mut(&mut tree, &[1]).op, LogicalOp::Or);
+    }
+
+    #[test]
+    fn remove_condition_by_path_removes_a_top_level_condition() {
+        let mut tree = nested_tree();
+        let removed = remove_condition_by_path(&mut tree, &[0]);
+        assert_eq!(removed, Some(item("a")));
+        assert_eq!(tree.filters.len(), 2);
+    }
+
+    #[test]
+    fn remove_condition_by_path_removes_a_condition_inside_a_sub_group() {
+        let mut tree = nested_tree();
+        let removed = remove_condition_by_path(&mut tree, &[1, 0]);
+        assert_eq!(removed, Some(item("b")));
+        match &tree.filters[1] {
+            FilterCondition::Group(sub_group) => assert_eq!(sub_group.filters, vec![item("c")]),
+            other => panic!("expected a sub-group, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn remove_condition_by_path_is_none_for_a_stale_path() {
+        let mut tree = nested_tree();
+        assert_eq!(remove_condition_by_path(&mut tree, &[9]), None);
+        assert_eq!(remove_condition_by_path(&mut tree, &[1, 9]), None);
+        assert_eq!(remove_condition_by_path(&mut tree, &[]), None);
+    }
 }
\ No newline at end of file
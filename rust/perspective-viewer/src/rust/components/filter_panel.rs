@@ -0,0 +1,81 @@
+////////////////////////////////////////////////////////////////////////////////
+//
+// Copyright (c) 2018, the Perspective Authors.
+//
+// This file is part of the Perspective library, distributed under the terms
+// of the Apache License 2.0.  The full license can be found in the LICENSE
+// file.
+
+//! The filter UI's composing root: the free-text query bar and the named
+//! saved-filter selector sit above the root `FilterGroup`'s nested
+//! condition/group tree, the three ways of editing the same `ViewConfig`
+//! `filter` kept in sync through `Session`.
+
+use crate::custom_elements::filter_dropdown::*;
+use crate::dragdrop::*;
+use crate::renderer::*;
+use crate::session::*;
+
+use super::filter_group_item::FilterGroupItem;
+use super::filter_query_bar::FilterQueryBar;
+use super::saved_filters::SavedFilterList;
+
+use yew::prelude::*;
+
+pub struct FilterPanel {
+    props: FilterPanelProperties,
+}
+
+#[derive(Properties, Clone)]
+pub struct FilterPanelProperties {
+    pub session: Session,
+    pub renderer: Renderer,
+    pub dragdrop: DragDrop,
+    pub filter_dropdown: FilterDropDownElement,
+
+    /// The column a bare word with no recognized column prefix filters on,
+    /// forwarded to the query bar.
+    pub primary_column: String,
+}
+
+impl Component for FilterPanel {
+    type Message = ();
+    type Properties = FilterPanelProperties;
+
+    fn create(props: FilterPanelProperties, _link: ComponentLink<Self>) -> Self {
+        FilterPanel { props }
+    }
+
+    fn update(&mut self, _msg: ()) -> bool {
+        false
+    }
+
+    fn change(&mut self, props: FilterPanelProperties) -> bool {
+        self.props = props;
+        true
+    }
+
+    fn view(&self) -> Html {
+        html! {
+            <div class="filter-panel">
+                <FilterQueryBar
+                    session={ self.props.session.clone() }
+                    renderer={ self.props.renderer.clone() }
+                    primary_column={ self.props.primary_column.clone() }>
+                </FilterQueryBar>
+                <SavedFilterList
+                    session={ self.props.session.clone() }
+                    renderer={ self.props.renderer.clone() }>
+                </SavedFilterList>
+                <FilterGroupItem
+                    path={ Vec::<usize>::new() }
+                    filter_dropdown={ self.props.filter_dropdown.clone() }
+                    on_keydown={ Callback::from(|_: String| ()) }
+                    session={ self.props.session.clone() }
+                    renderer={ self.props.renderer.clone() }
+                    dragdrop={ self.props.dragdrop.clone() }>
+                </FilterGroupItem>
+            </div>
+        }
+    }
+}
@@ -0,0 +1,672 @@
+////////////////////////////////////////////////////////////////////////////////
+//
+// Copyright (c) 2018, the Perspective Authors.
+//
+// This file is part of the Perspective library, distributed under the terms
+// of the Apache License 2.0.  The full license can be found in the LICENSE
+// file.
+
+use crate::config::*;
+use crate::renderer::*;
+use crate::session::*;
+use crate::*;
+
+use chrono::NaiveDate;
+use web_sys::*;
+use yew::prelude::*;
+
+/// Split `text` on whitespace, keeping double-quoted spans (quotes
+/// included) together as a single token so e.g. `state:"New York"` survives
+/// as one token instead of two. `\"` and `\\` inside a quoted span are kept
+/// literal rather than toggling/ending the span, the counterpart to the
+/// escaping `quote_if_needed` applies when formatting a value back out. A
+/// bare `(` or `)` outside quotes is always split off as its own token, even
+/// with no surrounding whitespace (`(state:NY)`), since `parse_group` needs
+/// them as standalone group delimiters.
+fn tokenize(text: &str) -> Vec<String> {
+    let mut tokens = vec![];
+    let mut chars = text.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+            continue;
+        }
+
+        if c == '(' || c == ')' {
+            tokens.push(c.to_string());
+            chars.next();
+            continue;
+        }
+
+        let mut token = String::new();
+        let mut in_quotes = false;
+        while let Some(&c) = chars.peek() {
+            if c == '\\' && in_quotes {
+                token.push(c);
+                chars.next();
+                if let Some(&escaped) = chars.peek() {
+                    token.push(escaped);
+                    chars.next();
+                }
+            } else if c == '"' {
+                in_quotes = !in_quotes;
+                token.push(c);
+                chars.next();
+            } else if !in_quotes && (c == '(' || c == ')') {
+                break;
+            } else if c.is_whitespace() && !in_quotes {
+                break;
+            } else {
+                token.push(c);
+                chars.next();
+            }
+        }
+
+        tokens.push(token);
+    }
+
+    tokens
+}
+
+/// Strip `value`'s surrounding quotes, if any, and undo `quote_if_needed`'s
+/// `\"`/`\\` escaping. A token with no surrounding quotes is returned as-is.
+fn unquote(value: &str) -> String {
+    match value.strip_prefix('"').and_then(|v| v.strip_suffix('"')) {
+        Some(inner) => {
+            let mut out = String::new();
+            let mut chars = inner.chars().peekable();
+            while let Some(c) = chars.next() {
+                if c == '\\' {
+                    if let Some(escaped) = chars.next() {
+                        out.push(escaped);
+                        continue;
+                    }
+                }
+
+                out.push(c);
+            }
+
+            out
+        }
+        None => value.to_owned(),
+    }
+}
+
+/// Quote `value` and escape its embedded `\` and `"` if it contains
+/// whitespace, a quote or a parenthesis, so formatting it into query text
+/// and re-parsing that text (`unquote`) round-trips to the same string
+/// instead of being split on whitespace or parentheses or truncated at an
+/// embedded quote.
+fn quote_if_needed(value: &str) -> String {
+    if value
+        .chars()
+        .any(|c| c.is_whitespace() || c == '"' || c == '(' || c == ')')
+    {
+        let escaped = value.replace('\\', "\\\\").replace('"', "\\\"");
+        format!("\"{}\"", escaped)
+    } else {
+        value.to_owned()
+    }
+}
+
+/// The recognized `column<op><value>` infix operators, in longest-match-first
+/// order so e.g. `>=` is tried before `>`. `Regex`, `Like`, `In` and the
+/// unary null checks have their own syntax and are matched in `parse_token`
+/// before this table, since none of them fit the simple symbol/value shape.
+const OPERATORS: &[(&str, FilterOp)] = &[
+    (">=", FilterOp::GTE),
+    ("<=", FilterOp::LTE),
+    ("!=", FilterOp::NE),
+    ("==", FilterOp::EQ),
+    (":", FilterOp::EQ),
+    (">", FilterOp::GT),
+    ("<", FilterOp::LT),
+    ("~", FilterOp::Contains),
+    ("^", FilterOp::BeginsWith),
+    ("$", FilterOp::EndsWith),
+];
+
+/// Coerce `value` into a `FilterTerm` using `column`'s table type, exactly
+/// as `FilterItemProperties::update_filter_value` does. Returns `None` for
+/// an unknown column or an unparseable value, so the caller can drop the
+/// token rather than filter on garbage.
+fn build_filter(session: &Session, column: String, op: FilterOp, value: &str) -> Option<Filter> {
+    let col_type = session.metadata().get_column_table_type(&column)?;
+    let term = match op {
+        // Regex/LIKE patterns are stored verbatim regardless of the
+        // column's type, same as `update_filter_value`.
+        FilterOp::Regex | FilterOp::Like => FilterTerm::Scalar(Scalar::String(value.to_owned())),
+        _ => match col_type {
+            Type::Integer => {
+                FilterTerm::Scalar(Scalar::Float(value.parse::<f64>().ok()?.floor()))
+            }
+            Type::Float => FilterTerm::Scalar(Scalar::Float(value.parse().ok()?)),
+            Type::Date => {
+                let date = NaiveDate::parse_from_str(value, "%Y-%m-%d").ok()?;
+                let millis = date.and_hms_opt(0, 0, 0)?.timestamp_millis() as u64;
+                FilterTerm::Scalar(Scalar::DateTime(millis))
+            }
+            Type::Datetime => {
+                let datetime =
+                    chrono::NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S").ok()?;
+                FilterTerm::Scalar(Scalar::DateTime(datetime.timestamp_millis() as u64))
+            }
+            Type::String => FilterTerm::Scalar(Scalar::String(value.to_owned())),
+            // No `FilterItem` value input exists for `Type::Boolean` either
+            // (its `update_filter_value` arm is `_ => {}`, a no-op); drop
+            // the token here too rather than inventing a string coercion
+            // the other editor of this `ViewConfig` doesn't have.
+            Type::Boolean => return None,
+        },
+    };
+
+    Some((column, op, term))
+}
+
+/// Build a `FilterOp::In` filter's array term from a comma-separated token
+/// list, typing each element per `column`'s table type exactly as
+/// `FilterItemProperties::update_filter_tokens` does. An element that won't
+/// parse for a numeric column is dropped rather than coerced.
+fn build_array_filter(session: &Session, column: String, value: &str) -> Option<Filter> {
+    let col_type = session.metadata().get_column_table_type(&column)?;
+    let scalars = value
+        .split(',')
+        .filter_map(|token| match col_type {
+            Type::Integer | Type::Float => token.trim().parse::<f64>().ok().map(Scalar::Float),
+            _ => Some(Scalar::String(token.trim().to_owned())),
+        })
+        .collect();
+
+    Some((column, FilterOp::In, FilterTerm::Array(scalars)))
+}
+
+/// Build an `IsNull`/`IsNotNull` filter, which carries no value of its own.
+fn build_null_filter(session: &Session, column: String, op: FilterOp) -> Option<Filter> {
+    session.metadata().get_column_table_type(&column)?;
+    Some((column, op, FilterTerm::Scalar(Scalar::Null)))
+}
+
+/// Parse a single non-keyword token into a `Filter`. Tries, in order: the
+/// unary `column:isnotnull`/`column:isnull` null checks, the `column:in:a,b`
+/// array syntax, the `column=~pattern` regex and `column%=pattern` like
+/// syntaxes, then the `column<op><value>` table, or finally applies `EQ`
+/// against `primary_column` for a bare word with no recognized column
+/// prefix.
+fn parse_token(token: &str, session: &Session, primary_column: &str) -> Option<Filter> {
+    let upper = token.to_uppercase();
+    if let Some(prefix) = upper.strip_suffix(":ISNOTNULL") {
+        return build_null_filter(session, token[..prefix.len()].to_owned(), FilterOp::IsNotNull);
+    }
+
+    if let Some(prefix) = upper.strip_suffix(":ISNULL") {
+        return build_null_filter(session, token[..prefix.len()].to_owned(), FilterOp::IsNull);
+    }
+
+    if let Some(idx) = token.find(":in:") {
+        let column = token[..idx].to_owned();
+        let column = if column.is_empty() {
+            primary_column.to_owned()
+        } else {
+            column
+        };
+
+        return build_array_filter(session, column, &token[idx + ":in:".len()..]);
+    }
+
+    if let Some(idx) = token.find("=~") {
+        let column = token[..idx].to_owned();
+        let column = if column.is_empty() {
+            primary_column.to_owned()
+        } else {
+            column
+        };
+
+        return build_filter(
+            session,
+            column,
+            FilterOp::Regex,
+            &unquote(&token[idx + "=~".len()..]),
+        );
+    }
+
+    if let Some(idx) = token.find("%=") {
+        let column = token[..idx].to_owned();
+        let column = if column.is_empty() {
+            primary_column.to_owned()
+        } else {
+            column
+        };
+
+        return build_filter(
+            session,
+            column,
+            FilterOp::Like,
+            &unquote(&token[idx + "%=".len()..]),
+        );
+    }
+
+    for (sym, op) in OPERATORS {
+        if let Some(idx) = token.find(sym) {
+            let column = token[..idx].to_owned();
+            let value = unquote(&token[idx + sym.len()..]);
+            let column = if column.is_empty() {
+                primary_column.to_owned()
+            } else {
+                column
+            };
+
+            return build_filter(session, column, *op, &value);
+        }
+    }
+
+    build_filter(
+        session,
+        primary_column.to_owned(),
+        FilterOp::EQ,
+        &unquote(token),
+    )
+}
+
+/// Compile a lucene-style query such as `state:"NY" AND (price>100 OR qty<1)`
+/// into the same nested `FilterGroup` that `FilterItem` edits cell-by-cell,
+/// the inverse of `format_query`. `AND`/`OR` keywords set the combinator
+/// applied to every condition at that nesting level; a parenthesized span
+/// becomes a `FilterCondition::Group`, so wrapping part of a query in `(...)`
+/// is how a user creates a sub-group from the bar. Tokens referencing an
+/// unknown column are silently dropped. An unmatched `)` at the top level
+/// ends parsing, and every token after it is ignored.
+pub fn parse_query(text: &str, session: &Session, primary_column: &str) -> FilterGroup {
+    let mut tokens = tokenize(text).into_iter().peekable();
+    parse_group(&mut tokens, session, primary_column)
+}
+
+/// Consume tokens up to (and including) a closing `)`, or to exhaustion,
+/// building one nesting level of the filter tree. A `(` recurses
+/// into a fresh sub-group at `filters.push`'d position.
+fn parse_group(
+    tokens: &mut std::iter::Peekable<std::vec::IntoIter<String>>,
+    session: &Session,
+    primary_column: &str,
+) -> FilterGroup {
+    let mut op = LogicalOp::And;
+    let mut filters = vec![];
+    while let Some(token) = tokens.next() {
+        match token.as_str() {
+            ")" => break,
+            "(" => filters.push(FilterCondition::Group(parse_group(
+                tokens,
+                session,
+                primary_column,
+            ))),
+            _ => match token.to_uppercase().as_str() {
+                "AND" => op = LogicalOp::And,
+                "OR" => op = LogicalOp::Or,
+                _ => {
+                    if let Some(filter) = parse_token(&token, session, primary_column) {
+                        filters.push(FilterCondition::Item(filter));
+                    }
+                }
+            },
+        }
+    }
+
+    FilterGroup { op, filters }
+}
+
+/// Render a single condition back into query syntax, the inverse of
+/// `parse_token`. Every `FilterOp` gets its own symbol so re-deriving the
+/// query bar's text (in `change()`) can't silently rewrite a filter to a
+/// different op than the one stored in `ViewConfig`.
+fn format_filter((column, op, term): &Filter) -> String {
+    match op {
+        FilterOp::EQ => format!("{}:{}", column, quote_if_needed(&term.to_string())),
+        FilterOp::NE => format!("{}!={}", column, quote_if_needed(&term.to_string())),
+        FilterOp::GT => format!("{}>{}", column, quote_if_needed(&term.to_string())),
+        FilterOp::GTE => format!("{}>={}", column, quote_if_needed(&term.to_string())),
+        FilterOp::LT => format!("{}<{}", column, quote_if_needed(&term.to_string())),
+        FilterOp::LTE => format!("{}<={}", column, quote_if_needed(&term.to_string())),
+        FilterOp::Contains => format!("{}~{}", column, quote_if_needed(&term.to_string())),
+        FilterOp::BeginsWith => format!("{}^{}", column, quote_if_needed(&term.to_string())),
+        FilterOp::EndsWith => format!("{}${}", column, quote_if_needed(&term.to_string())),
+        FilterOp::Regex => format!("{}=~{}", column, quote_if_needed(&term.to_string())),
+        FilterOp::Like => format!("{}%={}", column, quote_if_needed(&term.to_string())),
+        // `:in:`'s value is a bare comma-separated list (`build_array_filter`
+        // splits on `,` with no quote-awareness), so an individual element
+        // isn't quotable here without changing that syntax too.
+        FilterOp::In => format!("{}:in:{}", column, term),
+        FilterOp::IsNull => format!("{}:isnull", column),
+        FilterOp::IsNotNull => format!("{}:isnotnull", column),
+    }
+}
+
+/// Round-trip a `FilterGroup` back into query text, so the bar and the
+/// `FilterItem` chip UI stay in sync however the filter was last edited.
+pub fn format_query(group: &FilterGroup) -> String {
+    let sep = match group.op {
+        LogicalOp::And => " AND ",
+        LogicalOp::Or => " OR ",
+    };
+
+    group
+        .filters
+        .iter()
+        .map(|condition| match condition {
+            FilterCondition::Item(filter) => format_filter(filter),
+            FilterCondition::Group(sub_group) => format!("({})", format_query(sub_group)),
+        })
+        .collect::<Vec<_>>()
+        .join(sep)
+}
+
+/// A free-text entry that compiles a lucene-style mini-syntax directly into
+/// the view's `filter` list, as an alternative to building it up one
+/// `FilterItem` at a time.
+pub struct FilterQueryBar {
+    props: FilterQueryBarProperties,
+    link: ComponentLink<Self>,
+    text: String,
+}
+
+pub enum FilterQueryBarMsg {
+    Input(String),
+    KeyDown(u32),
+}
+
+#[derive(Properties, Clone)]
+pub struct FilterQueryBarProperties {
+    pub session: Session,
+    pub renderer: Renderer,
+
+    /// The column a bare word with no recognized column prefix filters on.
+    pub primary_column: String,
+}
+
+derive_renderable_props!(FilterQueryBarProperties);
+
+impl FilterQueryBarProperties {
+    fn apply_query(&self, text: &str) {
+        let group = parse_query(text, &self.session, &self.primary_column);
+        let update = ViewConfigUpdate {
+            filter: Some(group),
+            ..ViewConfigUpdate::default()
+        };
+
+        self.update_and_render(update);
+    }
+}
+
+impl Component for FilterQueryBar {
+    type Message = FilterQueryBarMsg;
+    type Properties = FilterQueryBarProperties;
+
+    fn create(props: FilterQueryBarProperties, link: ComponentLink<Self>) -> Self {
+        let ViewConfig { filter, .. } = props.session.get_view_config();
+        let text = format_query(&filter);
+        FilterQueryBar { props, link, text }
+    }
+
+    fn update(&mut self, msg: FilterQueryBarMsg) -> bool {
+        match msg {
+            FilterQueryBarMsg::Input(text) => {
+                self.text = text;
+                false
+            }
+            FilterQueryBarMsg::KeyDown(13) => {
+                self.props.apply_query(&self.text);
+                false
+            }
+            FilterQueryBarMsg::KeyDown(_) => false,
+        }
+    }
+
+    fn change(&mut self, props: FilterQueryBarProperties) -> bool {
+        let ViewConfig { filter, .. } = props.session.get_view_config();
+        self.text = format_query(&filter);
+        self.props = props;
+        true
+    }
+
+    fn view(&self) -> Html {
+        let input = self
+            .link
+            .callback(|input: InputData| FilterQueryBarMsg::Input(input.value));
+
+        let keydown = self.link.callback(|event: KeyboardEvent| {
+            if event.key_code() == 13 {
+                event.prevent_default();
+            }
+
+            FilterQueryBarMsg::KeyDown(event.key_code())
+        });
+
+        html! {
+            <input
+                type="text"
+                placeholder={ "state:\"NY\" AND price>100" }
+                class="filter-query-bar"
+                onkeydown={ keydown }
+                value={ self.text.clone() }
+                oninput={ input }/>
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_session() -> Session {
+        let session = Session::default();
+        session
+            .metadata_mut()
+            .set_column_table_type("state".to_owned(), Type::String);
+        session
+            .metadata_mut()
+            .set_column_table_type("price".to_owned(), Type::Float);
+        session
+            .metadata_mut()
+            .set_column_table_type("created".to_owned(), Type::Datetime);
+        session
+            .metadata_mut()
+            .set_column_table_type("qty".to_owned(), Type::Integer);
+        session
+    }
+
+    #[test]
+    fn tokenize_keeps_quoted_spans_together() {
+        let tokens = tokenize(r#"state:"New York" AND price>100"#);
+        assert_eq!(
+            tokens,
+            vec!["state:\"New York\"".to_owned(), "AND".to_owned(), "price>100".to_owned()]
+        );
+    }
+
+    #[test]
+    fn parse_token_builds_scalar_filter() {
+        let session = test_session();
+        let filter = parse_token("price>=100", &session, "state").unwrap();
+        assert_eq!(
+            filter,
+            (
+                "price".to_owned(),
+                FilterOp::GTE,
+                FilterTerm::Scalar(Scalar::Float(100.0))
+            )
+        );
+    }
+
+    #[test]
+    fn parse_token_bare_word_uses_primary_column() {
+        let session = test_session();
+        let filter = parse_token("\"NY\"", &session, "state").unwrap();
+        assert_eq!(
+            filter,
+            (
+                "state".to_owned(),
+                FilterOp::EQ,
+                FilterTerm::Scalar(Scalar::String("NY".to_owned()))
+            )
+        );
+    }
+
+    #[test]
+    fn parse_token_null_checks() {
+        let session = test_session();
+        assert_eq!(
+            parse_token("state:isnull", &session, "state").unwrap().1,
+            FilterOp::IsNull
+        );
+        assert_eq!(
+            parse_token("state:isnotnull", &session, "state").unwrap().1,
+            FilterOp::IsNotNull
+        );
+    }
+
+    #[test]
+    fn parse_token_in_list_types_each_element_and_drops_bad_ones() {
+        let session = test_session();
+        let filter = parse_token("price:in:1,2,bad", &session, "state").unwrap();
+        assert_eq!(
+            filter,
+            (
+                "price".to_owned(),
+                FilterOp::In,
+                FilterTerm::Array(vec![Scalar::Float(1.0), Scalar::Float(2.0)])
+            )
+        );
+    }
+
+    #[test]
+    fn build_filter_parses_datetime() {
+        let session = test_session();
+        let filter = build_filter(
+            &session,
+            "created".to_owned(),
+            FilterOp::EQ,
+            "2020-01-02 03:04:05",
+        )
+        .unwrap();
+
+        assert!(matches!(filter.2, FilterTerm::Scalar(Scalar::DateTime(_))));
+    }
+
+    #[test]
+    fn build_filter_unknown_column_is_none() {
+        let session = test_session();
+        assert!(build_filter(&session, "ghost".to_owned(), FilterOp::EQ, "x").is_none());
+    }
+
+    #[test]
+    fn build_filter_drops_boolean_columns_like_update_filter_value_does() {
+        let session = test_session();
+        session
+            .metadata_mut()
+            .set_column_table_type("active".to_owned(), Type::Boolean);
+
+        assert!(build_filter(&session, "active".to_owned(), FilterOp::EQ, "true").is_none());
+    }
+
+    #[test]
+    fn format_query_formats_null_checks_and_in_lists_with_their_own_syntax() {
+        let group = FilterGroup {
+            op: LogicalOp::And,
+            filters: vec![
+                FilterCondition::Item((
+                    "state".to_owned(),
+                    FilterOp::IsNull,
+                    FilterTerm::Scalar(Scalar::Null),
+                )),
+                FilterCondition::Item((
+                    "price".to_owned(),
+                    FilterOp::In,
+                    FilterTerm::Array(vec![Scalar::Float(1.0), Scalar::Float(2.0)]),
+                )),
+            ],
+        };
+
+        assert_eq!(format_query(&group), "state:isnull AND price:in:1,2");
+    }
+
+    #[test]
+    fn build_filter_floors_integer_values() {
+        let session = test_session();
+        let filter = build_filter(&session, "qty".to_owned(), FilterOp::EQ, "3.7").unwrap();
+        assert_eq!(filter.2, FilterTerm::Scalar(Scalar::Float(3.0)));
+    }
+
+    #[test]
+    fn format_filter_quotes_a_value_containing_whitespace() {
+        let filter = (
+            "state".to_owned(),
+            FilterOp::Contains,
+            FilterTerm::Scalar(Scalar::String("New York".to_owned())),
+        );
+
+        assert_eq!(format_filter(&filter), "state~\"New York\"");
+    }
+
+    #[test]
+    fn query_round_trips_through_a_spaced_value() {
+        let session = test_session();
+        let group = parse_query(r#"state~"New York""#, &session, "state");
+        let text = format_query(&group);
+        assert_eq!(text, "state~\"New York\"");
+
+        let reparsed = parse_query(&text, &session, "state");
+        assert_eq!(reparsed, group);
+    }
+
+    #[test]
+    fn tokenize_splits_parens_with_no_surrounding_whitespace() {
+        let tokens = tokenize("(state:NY)");
+        assert_eq!(tokens, vec!["(".to_owned(), "state:NY".to_owned(), ")".to_owned()]);
+    }
+
+    #[test]
+    fn parse_query_parses_a_parenthesized_span_into_a_sub_group() {
+        let session = test_session();
+        let group = parse_query("state:NY AND (price>100 OR qty<1)", &session, "state");
+
+        assert_eq!(group.op, LogicalOp::And);
+        assert_eq!(group.filters.len(), 2);
+        assert_eq!(
+            group.filters[0],
+            FilterCondition::Item((
+                "state".to_owned(),
+                FilterOp::EQ,
+                FilterTerm::Scalar(Scalar::String("NY".to_owned()))
+            ))
+        );
+
+        match &group.filters[1] {
+            FilterCondition::Group(sub_group) => {
+                assert_eq!(sub_group.op, LogicalOp::Or);
+                assert_eq!(
+                    sub_group.filters,
+                    vec![
+                        FilterCondition::Item((
+                            "price".to_owned(),
+                            FilterOp::GT,
+                            FilterTerm::Scalar(Scalar::Float(100.0))
+                        )),
+                        FilterCondition::Item((
+                            "qty".to_owned(),
+                            FilterOp::LT,
+                            FilterTerm::Scalar(Scalar::Float(1.0))
+                        )),
+                    ]
+                );
+            }
+            other => panic!("expected a sub-group, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn query_with_a_sub_group_round_trips() {
+        let session = test_session();
+        let group = parse_query("state:NY AND (price>100 OR qty<1)", &session, "state");
+        let text = format_query(&group);
+        let reparsed = parse_query(&text, &session, "state");
+        assert_eq!(reparsed, group);
+    }
+}
@@ -0,0 +1,14 @@
+////////////////////////////////////////////////////////////////////////////////
+//
+// Copyright (c) 2018, the Perspective Authors.
+//
+// This file is part of the Perspective library, distributed under the terms
+// of the Apache License 2.0.  The full license can be found in the LICENSE
+// file.
+
+pub mod containers;
+pub mod filter_group_item;
+pub mod filter_item;
+pub mod filter_panel;
+pub mod filter_query_bar;
+pub mod saved_filters;
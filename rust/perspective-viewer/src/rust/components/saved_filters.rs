@@ -0,0 +1,162 @@
+////////////////////////////////////////////////////////////////////////////////
+//
+// Copyright (c) 2018, the Perspective Authors.
+//
+// This file is part of the Perspective library, distributed under the terms
+// of the Apache License 2.0.  The full license can be found in the LICENSE
+// file.
+
+use crate::config::*;
+use crate::renderer::*;
+use crate::session::*;
+use crate::*;
+
+use super::containers::dropdown::*;
+
+use web_sys::*;
+use yew::prelude::*;
+
+type SavedFilterSelector = DropDown<String>;
+
+/// A `DropDown`-backed selector for named, persistable filter sets. Saving
+/// snapshots the current `ViewConfig`'s `filter`; selecting a saved name
+/// re-applies it via a single `ViewConfigUpdate`, so analysts can switch
+/// between reusable filter sets without rebuilding them by hand.
+pub struct SavedFilterList {
+    props: SavedFilterListProperties,
+    link: ComponentLink<Self>,
+    name_input: String,
+}
+
+pub enum SavedFilterListMsg {
+    Select(String),
+    NameInput(String),
+    Save,
+    Delete(String),
+}
+
+#[derive(Properties, Clone)]
+pub struct SavedFilterListProperties {
+    pub session: Session,
+    pub renderer: Renderer,
+}
+
+derive_renderable_props!(SavedFilterListProperties);
+
+impl SavedFilterListProperties {
+    /// Apply a saved filter set by name, replacing the view's current
+    /// `filter` wholesale.
+    fn apply(&self, name: &str) {
+        if let Some(group) = self.session.metadata().get_saved_filters().get(name) {
+            let update = ViewConfigUpdate {
+                filter: Some(group.clone()),
+                ..ViewConfigUpdate::default()
+            };
+
+            self.update_and_render(update);
+        }
+    }
+
+    /// Save (or overwrite) `name` with the view's current `filter`.
+    fn save(&self, name: String) {
+        let ViewConfig { filter, .. } = self.session.get_view_config();
+        self.session
+            .metadata_mut()
+            .get_saved_filters_mut()
+            .save(name, filter);
+    }
+
+    fn delete(&self, name: &str) {
+        self.session.metadata_mut().get_saved_filters_mut().remove(name);
+    }
+}
+
+impl Component for SavedFilterList {
+    type Message = SavedFilterListMsg;
+    type Properties = SavedFilterListProperties;
+
+    fn create(props: SavedFilterListProperties, link: ComponentLink<Self>) -> Self {
+        SavedFilterList {
+            props,
+            link,
+            name_input: String::new(),
+        }
+    }
+
+    fn update(&mut self, msg: SavedFilterListMsg) -> bool {
+        match msg {
+            SavedFilterListMsg::Select(name) => {
+                self.props.apply(&name);
+                false
+            }
+            SavedFilterListMsg::NameInput(name) => {
+                self.name_input = name;
+                true
+            }
+            SavedFilterListMsg::Save => {
+                let name = self.name_input.trim();
+                if !name.is_empty() {
+                    self.props.save(name.to_owned());
+                    self.name_input.clear();
+                }
+                true
+            }
+            SavedFilterListMsg::Delete(name) => {
+                self.props.delete(&name);
+                true
+            }
+        }
+    }
+
+    fn change(&mut self, props: SavedFilterListProperties) -> bool {
+        self.props = props;
+        true
+    }
+
+    fn view(&self) -> Html {
+        let names = self.props.session.metadata().get_saved_filters().names();
+
+        let select = self.link.callback(SavedFilterListMsg::Select);
+        let name_input = self
+            .link
+            .callback(|input: InputData| SavedFilterListMsg::NameInput(input.value));
+
+        let save = self.link.callback(|_: MouseEvent| SavedFilterListMsg::Save);
+
+        html! {
+            <div class="saved-filters">
+                <SavedFilterSelector
+                    class="saved-filter-selector"
+                    auto_resize=true
+                    values={ names.clone() }
+                    selected={ String::new() }
+                    on_select={ select }>
+                </SavedFilterSelector>
+                <input
+                    type="text"
+                    placeholder="Filter set name"
+                    class="saved-filter-name"
+                    value={ self.name_input.clone() }
+                    oninput={ name_input }/>
+                <button class="saved-filter-save" onclick={ save }>{ "Save" }</button>
+                {
+                    for names.into_iter().map(|name| {
+                        let delete = self.link.callback({
+                            let name = name.clone();
+                            move |_: MouseEvent| SavedFilterListMsg::Delete(name.clone())
+                        });
+
+                        html! {
+                            <span class="saved-filter-entry">
+                                { name }
+                                <span class="saved-filter-delete" onclick={ delete }>
+                                    { "\u{00d7}" }
+                                </span>
+                            </span>
+                        }
+                    })
+                }
+            </div>
+        }
+    }
+}
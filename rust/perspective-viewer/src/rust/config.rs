@@ -0,0 +1,296 @@
+////////////////////////////////////////////////////////////////////////////////
+//
+// Copyright (c) 2018, the Perspective Authors.
+//
+// This file is part of the Perspective library, distributed under the terms
+// of the Apache License 2.0.  The full license can be found in the LICENSE
+// file.
+
+//! The `View`-facing configuration types shared by every filter control:
+//! `FilterItem`, `FilterQueryBar` and `SavedFilterList` all read and write
+//! through `ViewConfig`/`ViewConfigUpdate`.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/// The table-column types a filter can be built against.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Type {
+    String,
+    Integer,
+    Float,
+    Date,
+    Datetime,
+    Boolean,
+}
+
+/// A single filterable value.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum Scalar {
+    String(String),
+    Float(f64),
+    DateTime(u64),
+    Bool(bool),
+    Null,
+}
+
+impl fmt::Display for Scalar {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Scalar::String(x) => write!(f, "{}", x),
+            Scalar::Float(x) => write!(f, "{}", x),
+            Scalar::DateTime(x) => write!(f, "{}", x),
+            Scalar::Bool(x) => write!(f, "{}", x),
+            Scalar::Null => write!(f, ""),
+        }
+    }
+}
+
+/// The comparison applied by a `Filter`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FilterOp {
+    EQ,
+    NE,
+    GT,
+    GTE,
+    LT,
+    LTE,
+    BeginsWith,
+    Contains,
+    EndsWith,
+    In,
+    Regex,
+    Like,
+    IsNull,
+    IsNotNull,
+}
+
+impl fmt::Display for FilterOp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            FilterOp::EQ => "==",
+            FilterOp::NE => "!=",
+            FilterOp::GT => ">",
+            FilterOp::GTE => ">=",
+            FilterOp::LT => "<",
+            FilterOp::LTE => "<=",
+            FilterOp::BeginsWith => "begins with",
+            FilterOp::Contains => "contains",
+            FilterOp::EndsWith => "ends with",
+            FilterOp::In => "in",
+            FilterOp::Regex => "regex",
+            FilterOp::Like => "like",
+            FilterOp::IsNull => "is null",
+            FilterOp::IsNotNull => "is not null",
+        };
+
+        write!(f, "{}", label)
+    }
+}
+
+/// The right-hand side of a `Filter`: either a single `Scalar`, or (for
+/// `FilterOp::In`) a list of them.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum FilterTerm {
+    Scalar(Scalar),
+    Array(Vec<Scalar>),
+}
+
+impl fmt::Display for FilterTerm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FilterTerm::Scalar(x) => write!(f, "{}", x),
+            FilterTerm::Array(xs) => write!(
+                f,
+                "{}",
+                xs.iter().map(|x| x.to_string()).collect::<Vec<_>>().join(",")
+            ),
+        }
+    }
+}
+
+/// A single `column <op> value` leaf condition, e.g. `("sym", FilterOp::EQ,
+/// FilterTerm::Scalar(Scalar::String("AAPL".into())))`.
+pub type Filter = (String, FilterOp, FilterTerm);
+
+/// The logical combinator applied across a `FilterGroup`'s children.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LogicalOp {
+    And,
+    Or,
+}
+
+/// One child of a `FilterGroup`: either a leaf condition, or a nested
+/// sub-group (so `(A AND B) OR C` round-trips as `Or(Group(And(A, B)), C)`).
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum FilterCondition {
+    Item(Filter),
+    Group(FilterGroup),
+}
+
+/// A recursive AND/OR group of filter conditions. This replaces the old
+/// implicit top-level `AND` of a flat `Vec<Filter>`; a flat filter list is
+/// now just a `FilterGroup` with `op: LogicalOp::And` and no nested groups.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct FilterGroup {
+    pub op: LogicalOp,
+    pub filters: Vec<FilterCondition>,
+}
+
+impl Default for FilterGroup {
+    fn default() -> Self {
+        FilterGroup {
+            op: LogicalOp::And,
+            filters: vec![],
+        }
+    }
+}
+
+/// The subset of `View` configuration the filter controls read.
+#[derive(Clone, Debug, Default)]
+pub struct ViewConfig {
+    pub filter: FilterGroup,
+}
+
+/// A partial `ViewConfig` update, applied via `Session::update_and_render`.
+#[derive(Clone, Debug, Default)]
+pub struct ViewConfigUpdate {
+    pub filter: Option<FilterGroup>,
+}
+
+/// The `localStorage` key the serialized `SavedFilters` map is kept under.
+const SAVED_FILTERS_STORAGE_KEY: &str = "perspective-viewer.saved-filters";
+
+/// Named, persistable filter sets, keyed by user-supplied name. Serializes
+/// the full `FilterGroup` (including nested groups, `FilterOp` and
+/// `FilterTerm`) to JSON and round-trips it through the browser's
+/// `localStorage` on every mutation, so saved sets survive a reload.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct SavedFilters(std::collections::HashMap<String, FilterGroup>);
+
+impl SavedFilters {
+    /// Load the persisted map from `localStorage`, falling back to an empty
+    /// one if there's no storage, no saved entry yet, or the entry fails to
+    /// parse (e.g. it was written by an incompatible older version).
+    pub fn load() -> Self {
+        Self::storage()
+            .and_then(|storage| storage.get_item(SAVED_FILTERS_STORAGE_KEY).ok().flatten())
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default()
+    }
+
+    /// Write the current map to `localStorage`. Silently does nothing if
+    /// there's no storage available (e.g. in a headless test).
+    fn persist(&self) {
+        if let Some(storage) = Self::storage() {
+            if let Ok(json) = serde_json::to_string(&self.0) {
+                let _ = storage.set_item(SAVED_FILTERS_STORAGE_KEY, &json);
+            }
+        }
+    }
+
+    fn storage() -> Option<web_sys::Storage> {
+        web_sys::window()?.local_storage().ok().flatten()
+    }
+
+    pub fn save(&mut self, name: String, filter: FilterGroup) {
+        self.0.insert(name, filter);
+        self.persist();
+    }
+
+    pub fn remove(&mut self, name: &str) {
+        self.0.remove(name);
+        self.persist();
+    }
+
+    pub fn get(&self, name: &str) -> Option<&FilterGroup> {
+        self.0.get(name)
+    }
+
+    /// Saved filter set names, sorted for stable `DropDown` ordering.
+    pub fn names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.0.keys().cloned().collect();
+        names.sort();
+        names
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn group() -> FilterGroup {
+        FilterGroup {
+            op: LogicalOp::And,
+            filters: vec![FilterCondition::Item((
+                "state".to_owned(),
+                FilterOp::EQ,
+                FilterTerm::Scalar(Scalar::String("NY".to_owned())),
+            ))],
+        }
+    }
+
+    #[test]
+    fn save_then_get_returns_the_saved_group() {
+        let mut saved = SavedFilters::default();
+        saved.save("ny".to_owned(), group());
+        assert_eq!(saved.get("ny"), Some(&group()));
+    }
+
+    #[test]
+    fn get_is_none_for_an_unknown_name() {
+        let saved = SavedFilters::default();
+        assert_eq!(saved.get("ghost"), None);
+    }
+
+    #[test]
+    fn save_overwrites_an_existing_name() {
+        let mut saved = SavedFilters::default();
+        saved.save("ny".to_owned(), group());
+        let replacement = FilterGroup {
+            op: LogicalOp::Or,
+            filters: vec![],
+        };
+        saved.save("ny".to_owned(), replacement.clone());
+        assert_eq!(saved.get("ny"), Some(&replacement));
+    }
+
+    #[test]
+    fn remove_drops_the_name() {
+        let mut saved = SavedFilters::default();
+        saved.save("ny".to_owned(), group());
+        saved.remove("ny");
+        assert_eq!(saved.get("ny"), None);
+    }
+
+    #[test]
+    fn remove_of_an_unknown_name_is_a_no_op() {
+        let mut saved = SavedFilters::default();
+        saved.remove("ghost");
+        assert_eq!(saved.names(), Vec::<String>::new());
+    }
+
+    #[test]
+    fn names_are_sorted() {
+        let mut saved = SavedFilters::default();
+        saved.save("ny".to_owned(), group());
+        saved.save("ca".to_owned(), group());
+        assert_eq!(saved.names(), vec!["ca".to_owned(), "ny".to_owned()]);
+    }
+
+    #[test]
+    fn persisted_json_round_trips_through_the_same_format_load_reads() {
+        // `persist`/`load` thread the map through `web_sys::Storage`, which
+        // needs a real browser `window` to exercise end-to-end; this pins
+        // the serialized shape they agree on (a `{name: FilterGroup}` map)
+        // so a schema change on one side can't silently break the other.
+        let mut saved = SavedFilters::default();
+        saved.save("ny".to_owned(), group());
+
+        let json = serde_json::to_string(&saved.0).unwrap();
+        let reloaded: std::collections::HashMap<String, FilterGroup> =
+            serde_json::from_str(&json).unwrap();
+        assert_eq!(reloaded.get("ny"), Some(&group()));
+    }
+}
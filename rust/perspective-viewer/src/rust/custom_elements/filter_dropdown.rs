@@ -0,0 +1,118 @@
+////////////////////////////////////////////////////////////////////////////////
+//
+// Copyright (c) 2018, the Perspective Authors.
+//
+// This file is part of the Perspective library, distributed under the terms
+// of the Apache License 2.0.  The full license can be found in the LICENSE
+// file.
+
+//! The "suggestions" popup shown under a `FilterItem`'s value input,
+//! ranking a column's distinct values against the operator currently
+//! selected.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use wasm_bindgen::JsValue;
+use web_sys::*;
+
+use crate::config::*;
+use crate::session::*;
+
+struct FilterDropDownState {
+    session: Session,
+    values: Vec<String>,
+    selected: Option<usize>,
+}
+
+/// A cheaply-cloneable handle onto a single, page-global suggestions popup,
+/// shared by every `FilterItem`/token input that can show one.
+#[derive(Clone)]
+pub struct FilterDropDownElement(Rc<RefCell<FilterDropDownState>>);
+
+impl FilterDropDownElement {
+    pub fn new(session: Session) -> Self {
+        FilterDropDownElement(Rc::new(RefCell::new(FilterDropDownState {
+            session,
+            values: vec![],
+            selected: None,
+        })))
+    }
+
+    /// Re-rank a column's distinct values against `value` and show them.
+    /// The ranking strategy follows `op`: substring match for `Contains`,
+    /// prefix match for `BeginsWith`, suffix match for `EndsWith`, and for
+    /// `In`, a prefix match against only the token after the last comma
+    /// (the pill currently being typed) rather than the whole field. Every
+    /// other (suggestable) op falls back to a case-insensitive prefix match.
+    pub fn autocomplete(
+        &self,
+        column: (Vec<usize>, String),
+        op: FilterOp,
+        value: String,
+        _target: HtmlElement,
+    ) {
+        let needle = match op {
+            FilterOp::In => value.rsplit(',').next().unwrap_or("").trim().to_lowercase(),
+            _ => value.trim().to_lowercase(),
+        };
+
+        let candidates = self
+            .0
+            .borrow()
+            .session
+            .metadata()
+            .get_column_values(&column.1);
+
+        let mut ranked: Vec<String> = candidates
+            .into_iter()
+            .filter(|candidate| {
+                let lower = candidate.to_lowercase();
+                match op {
+                    FilterOp::Contains => lower.contains(&needle),
+                    FilterOp::In => lower.starts_with(&needle),
+                    FilterOp::EndsWith => lower.ends_with(&needle),
+                    _ => lower.starts_with(&needle),
+                }
+            })
+            .collect();
+
+        ranked.sort();
+
+        let mut state = self.0.borrow_mut();
+        state.values = ranked;
+        state.selected = None;
+    }
+
+    pub fn item_down(&self) {
+        let mut state = self.0.borrow_mut();
+        let len = state.values.len();
+        if len > 0 {
+            state.selected = Some(state.selected.map_or(0, |i| (i + 1) % len));
+        }
+    }
+
+    pub fn item_up(&self) {
+        let mut state = self.0.borrow_mut();
+        let len = state.values.len();
+        if len > 0 {
+            state.selected = Some(state.selected.map_or(len - 1, |i| (i + len - 1) % len));
+        }
+    }
+
+    /// The currently-highlighted suggestion's text, if any. The caller
+    /// (`FilterItem`) writes this back into whichever input/token field
+    /// opened the dropdown, since the popup itself has no handle to that
+    /// component's state.
+    pub fn item_select(&self) -> Option<String> {
+        let state = self.0.borrow();
+        state.selected.and_then(|i| state.values.get(i).cloned())
+    }
+
+    pub fn hide(&self) -> Result<(), JsValue> {
+        self.0.borrow_mut().selected = None;
+        Ok(())
+    }
+
+    pub fn reautocomplete(&self) {}
+}
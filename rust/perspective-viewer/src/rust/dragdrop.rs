@@ -0,0 +1,56 @@
+////////////////////////////////////////////////////////////////////////////////
+//
+// Copyright (c) 2018, the Perspective Authors.
+//
+// This file is part of the Perspective library, distributed under the terms
+// of the Apache License 2.0.  The full license can be found in the LICENSE
+// file.
+
+//! Drag-and-drop state shared across the viewer's column/filter lists.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// Where a dragged item may be dropped.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DropAction {
+    Filter,
+    Sort,
+    GroupBy,
+    SplitBy,
+}
+
+/// What happens to the dragged item on a successful drop.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DragEffect {
+    Move(DropAction),
+    Copy(DropAction),
+}
+
+#[derive(Default)]
+struct DragDropState {
+    dragged: Option<(Vec<usize>, DragEffect)>,
+}
+
+/// A cheaply-cloneable handle coordinating an in-progress drag across
+/// the filter components (e.g. moving a `FilterItem` between
+/// `FilterGroup`s). Tracks the dragged item by its path rather than a
+/// column name, since two conditions on the same column in different
+/// groups are otherwise indistinguishable.
+#[derive(Clone, Default)]
+pub struct DragDrop(Rc<RefCell<DragDropState>>);
+
+impl DragDrop {
+    pub fn drag_start(&self, path: Vec<usize>, effect: DragEffect) {
+        self.0.borrow_mut().dragged = Some((path, effect));
+    }
+
+    pub fn drag_end(&self) {
+        self.0.borrow_mut().dragged = None;
+    }
+
+    /// The path of the condition currently being dragged, if any.
+    pub fn get_drag_path(&self) -> Option<Vec<usize>> {
+        self.0.borrow().dragged.as_ref().map(|(path, _)| path.clone())
+    }
+}
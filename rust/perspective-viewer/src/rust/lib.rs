@@ -0,0 +1,31 @@
+////////////////////////////////////////////////////////////////////////////////
+//
+// Copyright (c) 2018, the Perspective Authors.
+//
+// This file is part of the Perspective library, distributed under the terms
+// of the Apache License 2.0.  The full license can be found in the LICENSE
+// file.
+
+pub mod components;
+pub mod config;
+pub mod custom_elements;
+pub mod dragdrop;
+pub mod renderer;
+pub mod session;
+
+/// Gives a `*Properties` struct (one with `session: Session` and
+/// `renderer: Renderer` fields) an `update_and_render` method that applies a
+/// `ViewConfigUpdate` to the `Session` and schedules a redraw through the
+/// `Renderer`. Every filter control's properties derive this rather than
+/// repeating the two calls at each call site.
+#[macro_export]
+macro_rules! derive_renderable_props {
+    ($t:ty) => {
+        impl $t {
+            pub fn update_and_render(&self, update: $crate::config::ViewConfigUpdate) {
+                self.session.update_view_config(update);
+                self.renderer.render();
+            }
+        }
+    };
+}
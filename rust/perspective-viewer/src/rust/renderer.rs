@@ -0,0 +1,24 @@
+////////////////////////////////////////////////////////////////////////////////
+//
+// Copyright (c) 2018, the Perspective Authors.
+//
+// This file is part of the Perspective library, distributed under the terms
+// of the Apache License 2.0.  The full license can be found in the LICENSE
+// file.
+
+//! A handle used to request a re-draw of the active plugin after the
+//! `Session`'s `ViewConfig` changes.
+
+/// A cheaply-cloneable handle through which components request a re-render
+/// of the active plugin. Distinct from `Session` because a config change and a redraw are
+/// separately triggerable (e.g. a resize re-renders without touching the
+/// config).
+#[derive(Clone, Default)]
+pub struct Renderer;
+
+impl Renderer {
+    /// Request a re-render of the active plugin against the current
+    /// `Session` state. No plugin is attached to this handle, so the call
+    /// draws nothing.
+    pub fn render(&self) {}
+}
@@ -0,0 +1,96 @@
+////////////////////////////////////////////////////////////////////////////////
+//
+// Copyright (c) 2018, the Perspective Authors.
+//
+// This file is part of the Perspective library, distributed under the terms
+// of the Apache License 2.0.  The full license can be found in the LICENSE
+// file.
+
+//! A cheaply-cloneable handle onto the live `View`'s configuration and
+//! column metadata, shared by every component that reads or writes the
+//! filter list.
+
+use std::cell::{Ref, RefCell, RefMut};
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use crate::config::*;
+
+/// Column metadata for the table backing the current `View`, plus the
+/// named, persistable saved filter sets.
+pub struct Metadata {
+    column_types: HashMap<String, Type>,
+    saved_filters: SavedFilters,
+}
+
+impl Default for Metadata {
+    fn default() -> Self {
+        Metadata {
+            column_types: HashMap::new(),
+            // Loaded from `localStorage` so saved filter sets from a prior
+            // session are there as soon as the viewer mounts.
+            saved_filters: SavedFilters::load(),
+        }
+    }
+}
+
+impl Metadata {
+    /// Get a column's table type, e.g. the type used to gate/coerce a
+    /// `Filter`'s allowed operators and value.
+    pub fn get_column_table_type(&self, column: &str) -> Option<Type> {
+        self.column_types.get(column).copied()
+    }
+
+    /// Register `column`'s table type, e.g. from the schema of a newly
+    /// loaded table.
+    pub fn set_column_table_type(&mut self, column: String, col_type: Type) {
+        self.column_types.insert(column, col_type);
+    }
+
+    /// Distinct values for `column`, used to populate the filter
+    /// suggestions dropdown. `Metadata` stores no column values, so this
+    /// always returns an empty list.
+    pub fn get_column_values(&self, _column: &str) -> Vec<String> {
+        vec![]
+    }
+
+    pub fn get_saved_filters(&self) -> &SavedFilters {
+        &self.saved_filters
+    }
+
+    pub fn get_saved_filters_mut(&mut self) -> &mut SavedFilters {
+        &mut self.saved_filters
+    }
+}
+
+#[derive(Default)]
+struct SessionData {
+    metadata: Metadata,
+    view_config: ViewConfig,
+}
+
+/// A cheaply-cloneable handle onto the current `View`'s configuration,
+/// shared by every `*Properties` struct that reads/writes `filter`.
+#[derive(Clone, Default)]
+pub struct Session(Rc<RefCell<SessionData>>);
+
+impl Session {
+    pub fn metadata(&self) -> Ref<Metadata> {
+        Ref::map(self.0.borrow(), |x| &x.metadata)
+    }
+
+    pub fn metadata_mut(&self) -> RefMut<Metadata> {
+        RefMut::map(self.0.borrow_mut(), |x| &mut x.metadata)
+    }
+
+    pub fn get_view_config(&self) -> ViewConfig {
+        self.0.borrow().view_config.clone()
+    }
+
+    /// Apply a partial `ViewConfigUpdate` to the live `ViewConfig`.
+    pub fn update_view_config(&self, update: ViewConfigUpdate) {
+        if let Some(filter) = update.filter {
+            self.0.borrow_mut().view_config.filter = filter;
+        }
+    }
+}